@@ -0,0 +1,202 @@
+// Copyright 2024 Google, LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Command-line argument parsing for the analysis tool.
+
+use std::collections::HashMap;
+
+use crate::filter::PathFilter;
+
+/// Label used for extension-bucketing when a file has no extension.
+pub const NO_EXTENSION_LABEL: &str = "(no extension)";
+
+/// Output format for the codepoint census report.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    Text,
+    Json,
+    Csv,
+}
+
+/// Parsed command-line configuration.
+pub struct Config {
+    pub directory: String,
+    pub detect_confusables: bool,
+    pub path_filter: PathFilter,
+    pub max_depth: Option<usize>,
+    pub no_hidden: bool,
+    pub follow_links: bool,
+    pub format: OutputFormat,
+    pub extension_map: HashMap<String, String>,
+    pub scripts_path: Option<String>,
+}
+
+impl Config {
+    /// Buckets a file's extension: lowercased for case-insensitive
+    /// grouping, mapped to its canonical bucket if `--extension-map`
+    /// aliased it, or [`NO_EXTENSION_LABEL`] if the file has none.
+    pub fn bucket_extension(&self, extension: &str) -> String {
+        if extension.is_empty() {
+            return NO_EXTENSION_LABEL.to_string();
+        }
+        let lowercase = extension.to_lowercase();
+        self.extension_map
+            .get(&lowercase)
+            .cloned()
+            .unwrap_or(lowercase)
+    }
+}
+
+impl Config {
+    /// Parses `args` (the program's arguments, excluding argv\[0\]).
+    ///
+    /// Recognized flags:
+    /// - `--detect-confusables`: run the mixed-script/confusable report
+    ///   instead of the codepoint census.
+    /// - `--glob PATTERN`: only process paths matching PATTERN.
+    /// - `--exclude PATTERN`: skip paths matching PATTERN.
+    /// - `--depth N`: don't recurse past N directory levels.
+    /// - `--no-hidden`: skip dotfiles and dot-directories.
+    /// - `--follow-links`: follow symlinks while walking.
+    /// - `--format {text,json,csv}`: select the report format (default
+    ///   `text`).
+    /// - `--extension-map BUCKET=ext1,ext2,...`: alias related
+    ///   extensions (e.g. `cpp=cc,cpp,cxx`) into one bucket name.
+    /// - `--scripts-path PATH`: load the Unicode Script range table from
+    ///   a `Scripts.txt` file on disk instead of the bundled excerpt.
+    ///
+    /// Extensions are always bucketed case-insensitively (`.RS` and
+    /// `.rs` count together), and files with no extension are grouped
+    /// under [`NO_EXTENSION_LABEL`].
+    ///
+    /// `--glob` and `--exclude` may repeat; later occurrences (in the
+    /// order given on the command line) take precedence over earlier
+    /// ones when both match the same path.
+    pub fn parse<I: Iterator<Item = String>>(args: I) -> Self {
+        let mut directory = None;
+        let mut detect_confusables = false;
+        let mut glob_patterns: Vec<(String, bool)> = Vec::new();
+        let mut max_depth = None;
+        let mut no_hidden = false;
+        let mut follow_links = false;
+        let mut format = OutputFormat::Text;
+        let mut extension_map: HashMap<String, String> = HashMap::new();
+        let mut scripts_path = None;
+
+        let mut args = args.peekable();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--detect-confusables" => detect_confusables = true,
+                "--glob" => {
+                    let pattern = args.next().expect("Error: --glob requires a pattern");
+                    glob_patterns.push((pattern, true));
+                }
+                "--exclude" => {
+                    let pattern = args.next().expect("Error: --exclude requires a pattern");
+                    glob_patterns.push((pattern, false));
+                }
+                "--depth" => {
+                    let depth = args.next().expect("Error: --depth requires a number");
+                    max_depth = Some(depth.parse().expect("Error: --depth must be a number"));
+                }
+                "--no-hidden" => no_hidden = true,
+                "--follow-links" => follow_links = true,
+                "--format" => {
+                    let value = args.next().expect("Error: --format requires a value");
+                    format = match value.as_str() {
+                        "text" => OutputFormat::Text,
+                        "json" => OutputFormat::Json,
+                        "csv" => OutputFormat::Csv,
+                        other => panic!("Error: unknown --format value '{}'", other),
+                    };
+                }
+                "--extension-map" => {
+                    let value = args.next().expect("Error: --extension-map requires a value");
+                    let (bucket, aliases) = value
+                        .split_once('=')
+                        .expect("Error: --extension-map must be BUCKET=ext1,ext2,...");
+                    for alias in aliases.split(',') {
+                        extension_map.insert(alias.trim().to_lowercase(), bucket.to_string());
+                    }
+                }
+                "--scripts-path" => {
+                    scripts_path =
+                        Some(args.next().expect("Error: --scripts-path requires a path"));
+                }
+                _ if directory.is_none() => directory = Some(arg),
+                _ => {}
+            }
+        }
+
+        let path_filter =
+            PathFilter::build(&glob_patterns).expect("Error: invalid --glob/--exclude pattern");
+
+        Config {
+            directory: directory.expect("Error: Missing directory path argument"),
+            detect_confusables,
+            path_filter,
+            max_depth,
+            no_hidden,
+            follow_links,
+            format,
+            extension_map,
+            scripts_path,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Config {
+        Config::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn bucket_extension_merges_by_case() {
+        let config = parse(&["."]);
+        assert_eq!(config.bucket_extension("RS"), "rs");
+        assert_eq!(config.bucket_extension("rs"), "rs");
+        assert_eq!(config.bucket_extension("Rs"), "rs");
+    }
+
+    #[test]
+    fn bucket_extension_labels_no_extension() {
+        let config = parse(&["."]);
+        assert_eq!(config.bucket_extension(""), NO_EXTENSION_LABEL);
+    }
+
+    #[test]
+    fn bucket_extension_applies_extension_map_alias() {
+        let config = parse(&[".", "--extension-map", "cpp=cc,cpp,cxx"]);
+        assert_eq!(config.bucket_extension("cc"), "cpp");
+        assert_eq!(config.bucket_extension("CXX"), "cpp");
+        assert_eq!(config.bucket_extension("rs"), "rs");
+    }
+
+    #[test]
+    fn parse_reads_format_flag() {
+        let config = parse(&[".", "--format", "json"]);
+        assert_eq!(config.format, OutputFormat::Json);
+    }
+
+    #[test]
+    fn parse_reads_walk_control_flags() {
+        let config = parse(&[".", "--depth", "2", "--no-hidden", "--follow-links"]);
+        assert_eq!(config.max_depth, Some(2));
+        assert!(config.no_hidden);
+        assert!(config.follow_links);
+    }
+}
@@ -0,0 +1,122 @@
+// Copyright 2024 Google, LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Loads the UCD `confusables.txt` mapping and collapses runs of text to
+//! their TR39 "skeleton", so a non-ASCII run that resolves to the same
+//! skeleton as an ASCII identifier can be flagged as a homoglyph.
+
+use std::collections::HashMap;
+
+const EMBEDDED_CONFUSABLES_TXT: &str = include_str!("data/confusables.txt");
+
+/// Maps a single source codepoint to the prototype (one or more
+/// codepoints) UCD considers it confusable with.
+pub struct ConfusablesTable {
+    skeletons: HashMap<u32, String>,
+}
+
+impl ConfusablesTable {
+    /// Loads the `confusables.txt` excerpt bundled with this binary.
+    pub fn load_embedded() -> Self {
+        ConfusablesTable {
+            skeletons: parse_confusables_txt(EMBEDDED_CONFUSABLES_TXT),
+        }
+    }
+
+    /// Collapses `text` into its skeleton: every codepoint with a known
+    /// confusable mapping is replaced by its prototype, everything else
+    /// passes through unchanged.
+    pub fn skeleton(&self, text: &str) -> String {
+        text.chars()
+            .map(|c| {
+                self.skeletons
+                    .get(&(c as u32))
+                    .cloned()
+                    .unwrap_or_else(|| c.to_string())
+            })
+            .collect()
+    }
+}
+
+/// Parses the `source ; prototype ; MA # comment` lines of a
+/// `confusables.txt` file into a source-codepoint-to-prototype map.
+///
+/// `prototype` is frequently a sequence of several codepoints rather
+/// than one (e.g. `1D6A4 ; 0069 0307 ; MA`), so each whitespace-separated
+/// codepoint is decoded and the results are collapsed into one string.
+fn parse_confusables_txt(contents: &str) -> HashMap<u32, String> {
+    contents
+        .lines()
+        .filter_map(|line| {
+            let line = match line.find('#') {
+                Some(index) => &line[..index],
+                None => line,
+            };
+            let line = line.trim();
+            if line.is_empty() {
+                return None;
+            }
+            let mut fields = line.split(';');
+            let source = u32::from_str_radix(fields.next()?.trim(), 16).ok()?;
+            let prototype = fields.next()?.trim();
+            let prototype_str: String = prototype
+                .split_whitespace()
+                .map(|codepoint| -> Option<char> {
+                    char::from_u32(u32::from_str_radix(codepoint, 16).ok()?)
+                })
+                .collect::<Option<String>>()?;
+            Some((source, prototype_str))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collapses_cyrillic_lookalike_to_ascii() {
+        let table = ConfusablesTable::load_embedded();
+        assert_eq!(table.skeleton("\u{0430}dmin"), "admin");
+    }
+
+    #[test]
+    fn leaves_unmapped_text_unchanged() {
+        let table = ConfusablesTable::load_embedded();
+        assert_eq!(table.skeleton("hello"), "hello");
+    }
+
+    #[test]
+    fn partial_skeleton_match_still_has_non_ascii_remainder() {
+        let table = ConfusablesTable::load_embedded();
+        // U+0444 (CYRILLIC SMALL LETTER EF) has no confusable mapping in
+        // the bundled excerpt, so it survives the skeleton unchanged and
+        // the result isn't ASCII-only.
+        let skeleton = table.skeleton("\u{0430}\u{0444}");
+        assert_eq!(skeleton, "a\u{0444}");
+        assert!(!skeleton.is_ascii());
+    }
+
+    #[test]
+    fn parses_source_and_prototype_codepoints() {
+        let map = parse_confusables_txt("0430 ; 0061 ; MA # ( а → a )\n");
+        assert_eq!(map.get(&0x0430).map(String::as_str), Some("a"));
+    }
+
+    #[test]
+    fn parses_multi_codepoint_prototype_sequence() {
+        let map = parse_confusables_txt("1D6A4 ; 0069 0307 ; MA # MATHEMATICAL ITALIC SMALL DOTLESS I\n");
+        assert_eq!(map.get(&0x1D6A4).map(String::as_str), Some("i\u{0307}"));
+    }
+}
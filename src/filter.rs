@@ -0,0 +1,132 @@
+// Copyright 2024 Google, LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Gitignore-style include/exclude filtering for the directory walk,
+//! backed by `globset`. Patterns are compiled once, in the order they
+//! appeared on the command line, and the last pattern to match a given
+//! path decides whether it's included.
+
+use globset::{Glob, GlobMatcher};
+use std::path::Path;
+
+/// One compiled `--glob`/`--exclude` pattern plus whether a match
+/// includes (`true`) or excludes (`false`) the path.
+struct FilterRule {
+    matcher: GlobMatcher,
+    include: bool,
+}
+
+/// An ordered matcher over `--glob` and `--exclude` patterns. With no
+/// rules, every path is included; once at least one `--glob` rule
+/// exists, only paths matched by a rule are included by default.
+pub struct PathFilter {
+    rules: Vec<FilterRule>,
+    default_include: bool,
+}
+
+impl PathFilter {
+    /// Compiles `patterns` (in command-line order) into a filter.
+    /// `true` marks an include (`--glob`) pattern, `false` an exclude
+    /// (`--exclude`) pattern.
+    pub fn build(patterns: &[(String, bool)]) -> Result<Self, globset::Error> {
+        let rules = patterns
+            .iter()
+            .map(|(pattern, include)| {
+                Glob::new(pattern).map(|glob| FilterRule {
+                    matcher: glob.compile_matcher(),
+                    include: *include,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let default_include = !rules.iter().any(|rule| rule.include);
+        Ok(PathFilter {
+            rules,
+            default_include,
+        })
+    }
+
+    /// Reports whether `path` should be processed: the last rule whose
+    /// glob matches the path relative to `root`, the full path as
+    /// given, or the bare file name wins; with no matching rule, falls
+    /// back to `default_include`.
+    ///
+    /// Matching relative to `root` is what makes root-relative patterns
+    /// like `src/**` work regardless of how the directory argument was
+    /// spelled (`.`, an absolute path, a trailing slash, etc.).
+    pub fn is_match(&self, path: &Path, root: &Path) -> bool {
+        if self.rules.is_empty() {
+            return true;
+        }
+        let relative_path = path.strip_prefix(root).ok();
+        let file_name = path.file_name();
+        let mut include = self.default_include;
+        for rule in &self.rules {
+            let matches = relative_path.is_some_and(|relative| rule.matcher.is_match(relative))
+                || rule.matcher.is_match(path)
+                || file_name.is_some_and(|name| rule.matcher.is_match(name));
+            if matches {
+                include = rule.include;
+            }
+        }
+        include
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_patterns_includes_everything() {
+        let filter = PathFilter::build(&[]).unwrap();
+        assert!(filter.is_match(Path::new("/repo/src/main.rs"), Path::new("/repo")));
+    }
+
+    #[test]
+    fn root_relative_include_matches_src_glob() {
+        let filter = PathFilter::build(&[("src/**".to_string(), true)]).unwrap();
+        assert!(filter.is_match(Path::new("/repo/src/main.rs"), Path::new("/repo")));
+        assert!(!filter.is_match(Path::new("/repo/tests/lib.rs"), Path::new("/repo")));
+    }
+
+    #[test]
+    fn later_exclude_overrides_earlier_include() {
+        let filter = PathFilter::build(&[
+            ("src/**".to_string(), true),
+            ("**/vendor/**".to_string(), false),
+        ])
+        .unwrap();
+        assert!(filter.is_match(Path::new("/repo/src/main.rs"), Path::new("/repo")));
+        assert!(!filter.is_match(
+            Path::new("/repo/src/vendor/lib.rs"),
+            Path::new("/repo")
+        ));
+    }
+
+    #[test]
+    fn basename_only_pattern_matches_regardless_of_directory() {
+        let filter = PathFilter::build(&[("*.rs".to_string(), true)]).unwrap();
+        assert!(filter.is_match(Path::new("/repo/src/deep/nested/main.rs"), Path::new("/repo")));
+        assert!(!filter.is_match(Path::new("/repo/src/main.cc"), Path::new("/repo")));
+    }
+
+    #[test]
+    fn default_include_switches_to_exclude_once_an_include_rule_exists() {
+        let only_exclude = PathFilter::build(&[("*.log".to_string(), false)]).unwrap();
+        assert!(only_exclude.is_match(Path::new("/repo/main.rs"), Path::new("/repo")));
+
+        let with_include = PathFilter::build(&[("*.rs".to_string(), true)]).unwrap();
+        assert!(!with_include.is_match(Path::new("/repo/main.cc"), Path::new("/repo")));
+    }
+}
@@ -12,7 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::HashMap;
+mod cli;
+mod confusables;
+mod filter;
+mod report;
+mod script;
+
+use std::collections::{HashMap, HashSet};
 use std::env;
 use std::fs;
 use std::path::Path;
@@ -20,25 +26,57 @@ use unicode_categories::UnicodeCategories;
 use unicode_normalization::UnicodeNormalization;
 use unicode_segmentation::UnicodeSegmentation;
 
+use cli::{Config, OutputFormat};
+use confusables::ConfusablesTable;
+use script::ScriptTable;
+
 fn main() {
-    let directory_path = env::args()
-        .nth(1)
-        .expect("Error: Missing directory path argument");
+    let config = Config::parse(env::args().skip(1));
+
+    let script_table = match &config.scripts_path {
+        Some(path) => ScriptTable::load_from_path(Path::new(path))
+            .unwrap_or_else(|e| panic!("Error reading --scripts-path {}: {}", path, e)),
+        None => ScriptTable::load_embedded(),
+    };
+
+    if config.detect_confusables {
+        let confusables_table = ConfusablesTable::load_embedded();
+        for entry in build_walker(&config) {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(e) => {
+                    eprintln!("Error walking directory: {}", e);
+                    continue;
+                }
+            };
+            let path = entry.path();
+            if path.is_file() && config.path_filter.is_match(path, Path::new(&config.directory)) {
+                for finding in detect_confusables_in_file(path, &script_table, &confusables_table) {
+                    println!("{}", finding);
+                }
+            }
+        }
+        return;
+    }
 
     let mut codepoint_counts_by_extension: HashMap<String, HashMap<u32, u128>> = HashMap::new();
     let mut total_chars_by_extension: HashMap<String, u128> = HashMap::new();
     let mut ascii_chars_by_extension: HashMap<String, u128> = HashMap::new();
 
     // Walk the directory recursively
-    for entry in walkdir::WalkDir::new(directory_path) {
-        let entry = entry.unwrap();
+    for entry in build_walker(&config) {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                eprintln!("Error walking directory: {}", e);
+                continue;
+            }
+        };
         let path = entry.path();
-        if path.is_file() {
-            let extension = path
-                .extension()
-                .unwrap_or_default()
-                .to_string_lossy()
-                .to_string();
+        if path.is_file() && config.path_filter.is_match(path, Path::new(&config.directory)) {
+            let extension = config.bucket_extension(
+                &path.extension().unwrap_or_default().to_string_lossy(),
+            );
             process_file(
                 path,
                 &extension,
@@ -49,33 +87,42 @@ fn main() {
         }
     }
 
-    for (extension, codepoint_counts) in codepoint_counts_by_extension {
-        println!("\nFile Extension: {}", extension);
-        let mut count_vec: Vec<(u32, u128)> = codepoint_counts.into_iter().collect();
-        count_vec.sort_by(|a, b| b.1.cmp(&a.1));
+    let reports = report::build_reports(
+        codepoint_counts_by_extension,
+        &total_chars_by_extension,
+        &ascii_chars_by_extension,
+        &script_table,
+        get_character_category,
+    );
 
-        for (codepoint, count) in count_vec {
-            let character = char::from_u32(codepoint).unwrap_or(char::REPLACEMENT_CHARACTER);
-            let category = get_character_category(character);
-            let is_ascii = codepoint <= 0x7F;
-            println!(
-                "Character: {}, Codepoint: {:04x}, Category: {:?}, ASCII: {}, Count: {}",
-                character, codepoint, category, is_ascii, count
-            );
-        }
+    match config.format {
+        OutputFormat::Text => report::print_text(&reports),
+        OutputFormat::Json => report::print_json(&reports).expect("Error: failed to write JSON"),
+        OutputFormat::Csv => report::print_csv(&reports).expect("Error: failed to write CSV"),
+    }
+}
 
-        let total_chars = total_chars_by_extension.get(&extension).unwrap_or(&0);
-        let ascii_chars = ascii_chars_by_extension.get(&extension).unwrap_or(&0);
-        let ascii_percent = (*ascii_chars as f64 / *total_chars as f64) * 100.0;
-        let non_ascii_percent = 100.0 - ascii_percent;
-        println!("\nSummary for .{} files:", extension);
-        println!("  ASCII encodings: {} ({:.2}%)", ascii_chars, ascii_percent);
-        println!(
-            "  Non-ASCII encodings: {} ({:.2}%)",
-            total_chars - ascii_chars,
-            non_ascii_percent
-        );
+/// Builds a `WalkDir` iterator honoring `--depth`, `--no-hidden`, and
+/// `--follow-links`. Hidden entries are pruned with `filter_entry` so
+/// hidden directories aren't descended into, not merely skipped once
+/// reached.
+fn build_walker(config: &Config) -> impl Iterator<Item = walkdir::Result<walkdir::DirEntry>> + '_ {
+    let mut walker = walkdir::WalkDir::new(&config.directory).follow_links(config.follow_links);
+    if let Some(max_depth) = config.max_depth {
+        walker = walker.max_depth(max_depth);
     }
+    walker.into_iter().filter_entry(move |entry| {
+        // The walk root itself is never pruned, so a directory argument
+        // spelled `.` or `..` is still walked.
+        if !config.no_hidden || entry.depth() == 0 {
+            return true;
+        }
+        entry
+            .file_name()
+            .to_str()
+            .map(|name| !name.starts_with('.'))
+            .unwrap_or(true)
+    })
 }
 
 fn process_file(
@@ -94,7 +141,7 @@ fn process_file(
                     let codepoint = c as u32;
                     *codepoint_counts_by_extension
                         .entry(extension.to_string())
-                        .or_insert_with(HashMap::new)
+                        .or_default()
                         .entry(codepoint)
                         .or_insert(0) += 1;
                     *total_chars_by_extension
@@ -112,6 +159,70 @@ fn process_file(
     }
 }
 
+/// Flags mixed-script content and TR39-style confusables in a single
+/// file: letters drawn from more than one unexpected script, and
+/// identifier-like runs whose skeleton collides with an ASCII prototype.
+///
+/// Returns the formatted finding lines rather than printing them
+/// directly, so the detection logic can be exercised without capturing
+/// stdout.
+fn detect_confusables_in_file(
+    path: &Path,
+    script_table: &ScriptTable,
+    confusables_table: &ConfusablesTable,
+) -> Vec<String> {
+    let mut findings = Vec::new();
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => {
+            eprintln!("Error reading file {}: {}", path.display(), e);
+            return findings;
+        }
+    };
+    let normalized_content = content.nfc().collect::<String>();
+
+    let mut unexpected_scripts: HashSet<&str> = HashSet::new();
+    for c in normalized_content.chars() {
+        if c.is_letter() {
+            let script_name = script_table.lookup(c as u32);
+            if script_name != "Common" && script_name != "Inherited" && script_name != "Latin" {
+                unexpected_scripts.insert(script_name);
+            }
+        }
+    }
+    if unexpected_scripts.len() > 1 {
+        let mut scripts: Vec<&str> = unexpected_scripts.into_iter().collect();
+        scripts.sort_unstable();
+        findings.push(format!(
+            "[mixed-script] {}: mixes scripts {}",
+            path.display(),
+            scripts.join(", ")
+        ));
+    }
+
+    for token in normalized_content.split(|c: char| !(c.is_alphanumeric() || c == '_')) {
+        if token.is_empty() || token.is_ascii() {
+            continue;
+        }
+        let skeleton = confusables_table.skeleton(token);
+        if skeleton.is_ascii() {
+            for c in token.chars().filter(|c| !c.is_ascii()) {
+                let script_name = script_table.lookup(c as u32);
+                findings.push(format!(
+                    "[confusable] {}: '{}' (U+{:04X}, {}) in identifier '{}' resembles '{}'",
+                    path.display(),
+                    c,
+                    c as u32,
+                    script_name,
+                    token,
+                    skeleton
+                ));
+            }
+        }
+    }
+    findings
+}
+
 fn get_character_category(c: char) -> &'static str {
     if c.is_letter() || c.is_number() {
         "Alphanumeric"
@@ -125,3 +236,172 @@ fn get_character_category(c: char) -> &'static str {
         "Other"
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cli::OutputFormat;
+    use filter::PathFilter;
+    use std::fs;
+    use std::path::PathBuf;
+
+    /// Builds a fresh temp directory for one test, namespaced by the
+    /// test name and process id so parallel test runs don't collide.
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "srcanalysis_test_{}_{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn config_for(directory: &Path, no_hidden: bool, max_depth: Option<usize>) -> Config {
+        Config {
+            directory: directory.to_string_lossy().to_string(),
+            detect_confusables: false,
+            path_filter: PathFilter::build(&[]).unwrap(),
+            max_depth,
+            no_hidden,
+            follow_links: false,
+            format: OutputFormat::Text,
+            extension_map: HashMap::new(),
+            scripts_path: None,
+        }
+    }
+
+    fn walked_file_names(config: &Config) -> HashSet<String> {
+        build_walker(config)
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().is_file())
+            .map(|entry| {
+                entry
+                    .path()
+                    .strip_prefix(&config.directory)
+                    .unwrap()
+                    .to_string_lossy()
+                    .replace('\\', "/")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn build_walker_prunes_hidden_files_and_directories() {
+        let root = temp_dir("no_hidden");
+        fs::write(root.join("visible.txt"), "x").unwrap();
+        fs::write(root.join(".hidden.txt"), "x").unwrap();
+        fs::create_dir_all(root.join(".hidden_dir")).unwrap();
+        fs::write(root.join(".hidden_dir").join("child.txt"), "x").unwrap();
+        fs::create_dir_all(root.join("sub")).unwrap();
+        fs::write(root.join("sub").join("nested.txt"), "x").unwrap();
+
+        let config = config_for(&root, true, None);
+        let names = walked_file_names(&config);
+
+        assert!(names.contains("visible.txt"));
+        assert!(names.contains("sub/nested.txt"));
+        assert!(!names.contains(".hidden.txt"));
+        assert!(!names.contains(".hidden_dir/child.txt"));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn build_walker_honors_max_depth() {
+        let root = temp_dir("max_depth");
+        fs::create_dir_all(root.join("sub").join("deep")).unwrap();
+        fs::write(root.join("sub").join("nested.txt"), "x").unwrap();
+        fs::write(root.join("sub").join("deep").join("deepest.txt"), "x").unwrap();
+
+        // root=0, sub=1, sub/deep=2, sub/deep/deepest.txt=3
+        let shallow = config_for(&root, false, Some(2));
+        let names = walked_file_names(&shallow);
+        assert!(names.contains("sub/nested.txt"));
+        assert!(!names.contains("sub/deep/deepest.txt"));
+
+        let unbounded = config_for(&root, false, None);
+        let names = walked_file_names(&unbounded);
+        assert!(names.contains("sub/deep/deepest.txt"));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn build_walker_follows_links_only_when_enabled() {
+        use std::os::unix::fs::symlink;
+
+        let root = temp_dir("follow_links");
+        fs::create_dir_all(root.join("sub")).unwrap();
+        fs::write(root.join("sub").join("nested.txt"), "x").unwrap();
+        symlink(root.join("sub"), root.join("link_to_sub")).unwrap();
+
+        let not_following = config_for(&root, false, None);
+        let names = walked_file_names(&not_following);
+        assert!(!names.contains("link_to_sub/nested.txt"));
+
+        let mut following = config_for(&root, false, None);
+        following.follow_links = true;
+        let names = walked_file_names(&following);
+        assert!(names.contains("link_to_sub/nested.txt"));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn detect_confusables_flags_mixed_script_content() {
+        let root = temp_dir("mixed_script");
+        let file = root.join("mixed.txt");
+        // Greek alpha and Cyrillic be: two distinct unexpected scripts.
+        fs::write(&file, "\u{03B1}\u{0431}").unwrap();
+
+        let script_table = ScriptTable::load_embedded();
+        let confusables_table = ConfusablesTable::load_embedded();
+        let findings = detect_confusables_in_file(&file, &script_table, &confusables_table);
+
+        assert_eq!(findings.len(), 1);
+        assert!(findings[0].starts_with("[mixed-script]"));
+        assert!(findings[0].contains("Cyrillic"));
+        assert!(findings[0].contains("Greek"));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn detect_confusables_does_not_flag_single_unexpected_script() {
+        // Only one unexpected script present, so the mixed-script
+        // threshold (`unexpected_scripts.len() > 1`) isn't crossed.
+        let root = temp_dir("single_script");
+        let file = root.join("single.txt");
+        fs::write(&file, "\u{03B1}\u{03B2}").unwrap();
+
+        let script_table = ScriptTable::load_embedded();
+        let confusables_table = ConfusablesTable::load_embedded();
+        let findings = detect_confusables_in_file(&file, &script_table, &confusables_table);
+
+        assert!(findings.iter().all(|f| !f.starts_with("[mixed-script]")));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn detect_confusables_flags_ascii_lookalike_identifier() {
+        let root = temp_dir("confusable");
+        let file = root.join("confusable.txt");
+        // Cyrillic "а" (U+0430) skeletonizes to ASCII "a", so "\u{0430}dmin"
+        // resembles the identifier "admin".
+        fs::write(&file, "\u{0430}dmin").unwrap();
+
+        let script_table = ScriptTable::load_embedded();
+        let confusables_table = ConfusablesTable::load_embedded();
+        let findings = detect_confusables_in_file(&file, &script_table, &confusables_table);
+
+        assert_eq!(findings.len(), 1);
+        assert!(findings[0].starts_with("[confusable]"));
+        assert!(findings[0].contains("admin"));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,279 @@
+// Copyright 2024 Google, LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Typed report structures built from the collected codepoint counts,
+//! and the `text`/`json`/`csv` formatters that consume them. Keeping
+//! collection and formatting separate lets every output mode share one
+//! pass over the data.
+
+use std::collections::HashMap;
+use std::io;
+
+use serde::Serialize;
+
+use crate::script::ScriptTable;
+
+/// One codepoint's tallied stats within a single extension.
+#[derive(Serialize)]
+pub struct CharStat {
+    pub codepoint: u32,
+    pub hex: String,
+    pub character: String,
+    pub category: &'static str,
+    pub script: String,
+    pub is_ascii: bool,
+    pub count: u128,
+}
+
+/// A script's share of the characters counted for an extension.
+#[derive(Serialize)]
+pub struct ScriptShare {
+    pub script: String,
+    pub percent: f64,
+}
+
+/// The full report for one file extension: every counted codepoint plus
+/// the ASCII and script composition summaries.
+#[derive(Serialize)]
+pub struct ExtensionReport {
+    pub extension: String,
+    pub entries: Vec<CharStat>,
+    pub total: u128,
+    pub ascii: u128,
+    pub script_breakdown: Vec<ScriptShare>,
+}
+
+/// Builds one [`ExtensionReport`] per extension from the accumulated
+/// counts, sorted by descending codepoint frequency within each.
+pub fn build_reports(
+    codepoint_counts_by_extension: HashMap<String, HashMap<u32, u128>>,
+    total_chars_by_extension: &HashMap<String, u128>,
+    ascii_chars_by_extension: &HashMap<String, u128>,
+    script_table: &ScriptTable,
+    get_character_category: impl Fn(char) -> &'static str,
+) -> Vec<ExtensionReport> {
+    let mut reports: Vec<ExtensionReport> = codepoint_counts_by_extension
+        .into_iter()
+        .map(|(extension, codepoint_counts)| {
+            let mut count_vec: Vec<(u32, u128)> = codepoint_counts.into_iter().collect();
+            count_vec.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+
+            let total = *total_chars_by_extension.get(&extension).unwrap_or(&0);
+            let ascii = *ascii_chars_by_extension.get(&extension).unwrap_or(&0);
+
+            let mut script_counts: HashMap<&str, u128> = HashMap::new();
+            let entries: Vec<CharStat> = count_vec
+                .into_iter()
+                .map(|(codepoint, count)| {
+                    let character = char::from_u32(codepoint).unwrap_or(char::REPLACEMENT_CHARACTER);
+                    let script_name = script_table.lookup(codepoint);
+                    *script_counts.entry(script_name).or_insert(0) += count;
+                    CharStat {
+                        codepoint,
+                        hex: format!("{:04x}", codepoint),
+                        character: character.to_string(),
+                        category: get_character_category(character),
+                        script: script_name.to_string(),
+                        is_ascii: codepoint <= 0x7F,
+                        count,
+                    }
+                })
+                .collect();
+
+            let mut script_breakdown: Vec<ScriptShare> = script_counts
+                .into_iter()
+                .map(|(script, count)| ScriptShare {
+                    script: script.to_string(),
+                    percent: (count as f64 / total as f64) * 100.0,
+                })
+                .collect();
+            script_breakdown.sort_by(|a, b| b.percent.partial_cmp(&a.percent).unwrap());
+
+            ExtensionReport {
+                extension,
+                entries,
+                total,
+                ascii,
+                script_breakdown,
+            }
+        })
+        .collect();
+    reports.sort_by(|a, b| a.extension.cmp(&b.extension));
+    reports
+}
+
+/// Renders reports in the tool's original human-readable format.
+pub fn print_text(reports: &[ExtensionReport]) {
+    for report in reports {
+        println!("\nFile Extension: {}", report.extension);
+        for entry in &report.entries {
+            println!(
+                "Character: {}, Codepoint: {}, Category: {:?}, Script: {}, ASCII: {}, Count: {}",
+                entry.character, entry.hex, entry.category, entry.script, entry.is_ascii, entry.count
+            );
+        }
+
+        let ascii_percent = (report.ascii as f64 / report.total as f64) * 100.0;
+        let non_ascii_percent = 100.0 - ascii_percent;
+        println!("\nSummary for .{} files:", report.extension);
+        println!(
+            "  ASCII encodings: {} ({:.2}%)",
+            report.ascii, ascii_percent
+        );
+        println!(
+            "  Non-ASCII encodings: {} ({:.2}%)",
+            report.total - report.ascii,
+            non_ascii_percent
+        );
+
+        let script_breakdown: Vec<String> = report
+            .script_breakdown
+            .iter()
+            .map(|share| format!("{}: {:.1}%", share.script, share.percent))
+            .collect();
+        println!("  Scripts: {}", script_breakdown.join(", "));
+    }
+}
+
+/// Serializes reports as pretty-printed JSON.
+pub fn print_json(reports: &[ExtensionReport]) -> serde_json::Result<()> {
+    println!("{}", serde_json::to_string_pretty(reports)?);
+    Ok(())
+}
+
+/// A single CSV row: one counted codepoint plus its extension's summary
+/// percentages, since CSV has no room for `ExtensionReport`'s nesting.
+#[derive(Serialize)]
+struct CsvRow<'a> {
+    extension: &'a str,
+    codepoint: u32,
+    hex: &'a str,
+    category: &'a str,
+    script: &'a str,
+    is_ascii: bool,
+    count: u128,
+    extension_total: u128,
+    extension_ascii: u128,
+    extension_ascii_percent: f64,
+}
+
+/// Serializes reports as CSV, one row per counted codepoint.
+pub fn print_csv(reports: &[ExtensionReport]) -> csv::Result<()> {
+    let mut writer = csv::Writer::from_writer(io::stdout());
+    for report in reports {
+        let ascii_percent = (report.ascii as f64 / report.total as f64) * 100.0;
+        for entry in &report.entries {
+            writer.serialize(CsvRow {
+                extension: &report.extension,
+                codepoint: entry.codepoint,
+                hex: &entry.hex,
+                category: entry.category,
+                script: &entry.script,
+                is_ascii: entry.is_ascii,
+                count: entry.count,
+                extension_total: report.total,
+                extension_ascii: report.ascii,
+                extension_ascii_percent: ascii_percent,
+            })?;
+        }
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::script::ScriptTable;
+
+    fn category(c: char) -> &'static str {
+        if c.is_alphabetic() {
+            "Alphanumeric"
+        } else {
+            "Other"
+        }
+    }
+
+    #[test]
+    fn entries_sorted_by_descending_count() {
+        let mut codepoint_counts_by_extension = HashMap::new();
+        codepoint_counts_by_extension.insert(
+            "rs".to_string(),
+            HashMap::from([('a' as u32, 1), ('b' as u32, 5)]),
+        );
+        let total = HashMap::from([("rs".to_string(), 6)]);
+        let ascii = HashMap::from([("rs".to_string(), 6)]);
+        let script_table = ScriptTable::load_embedded();
+
+        let reports = build_reports(codepoint_counts_by_extension, &total, &ascii, &script_table, category);
+
+        assert_eq!(reports.len(), 1);
+        let entries = &reports[0].entries;
+        assert_eq!(entries[0].codepoint, 'b' as u32);
+        assert_eq!(entries[0].count, 5);
+        assert_eq!(entries[1].codepoint, 'a' as u32);
+    }
+
+    #[test]
+    fn reports_sorted_by_extension_name() {
+        let mut codepoint_counts_by_extension = HashMap::new();
+        codepoint_counts_by_extension.insert("rs".to_string(), HashMap::from([('a' as u32, 1)]));
+        codepoint_counts_by_extension.insert("cc".to_string(), HashMap::from([('a' as u32, 1)]));
+        let total = HashMap::from([("rs".to_string(), 1), ("cc".to_string(), 1)]);
+        let ascii = total.clone();
+        let script_table = ScriptTable::load_embedded();
+
+        let reports = build_reports(codepoint_counts_by_extension, &total, &ascii, &script_table, category);
+
+        assert_eq!(reports[0].extension, "cc");
+        assert_eq!(reports[1].extension, "rs");
+    }
+
+    #[test]
+    fn script_breakdown_percentages_sum_to_total() {
+        let mut codepoint_counts_by_extension = HashMap::new();
+        codepoint_counts_by_extension.insert(
+            "rs".to_string(),
+            HashMap::from([('a' as u32, 3), (0x0430, 1)]),
+        );
+        let total = HashMap::from([("rs".to_string(), 4)]);
+        let ascii = HashMap::from([("rs".to_string(), 3)]);
+        let script_table = ScriptTable::load_embedded();
+
+        let reports = build_reports(codepoint_counts_by_extension, &total, &ascii, &script_table, category);
+
+        let breakdown = &reports[0].script_breakdown;
+        let total_percent: f64 = breakdown.iter().map(|share| share.percent).sum();
+        assert!((total_percent - 100.0).abs() < 1e-9);
+        assert_eq!(breakdown[0].script, "Latin");
+        assert_eq!(breakdown[0].percent, 75.0);
+    }
+
+    #[test]
+    fn char_stat_serializes_expected_fields() {
+        let stat = CharStat {
+            codepoint: 0x61,
+            hex: "0061".to_string(),
+            character: "a".to_string(),
+            category: "Alphanumeric",
+            script: "Latin".to_string(),
+            is_ascii: true,
+            count: 2,
+        };
+        let value = serde_json::to_value(&stat).unwrap();
+        assert_eq!(value["hex"], "0061");
+        assert_eq!(value["script"], "Latin");
+        assert_eq!(value["count"], 2);
+    }
+}
@@ -0,0 +1,148 @@
+// Copyright 2024 Google, LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Resolves codepoints to their Unicode Script property by parsing the
+//! UCD `Scripts.txt` format and binary-searching a sorted range table.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// A codepoint range tagged with the name of its Unicode Script, sorted
+/// by `start` so lookups can binary search instead of scanning linearly.
+pub struct ScriptTable {
+    ranges: Vec<(u32, u32, String)>,
+}
+
+const EMBEDDED_SCRIPTS_TXT: &str = include_str!("data/Scripts.txt");
+
+/// Script name returned for codepoints that fall outside every range in
+/// the table, e.g. unassigned or private-use codepoints.
+pub const UNKNOWN_SCRIPT: &str = "Unknown";
+
+impl ScriptTable {
+    /// Loads the `Scripts.txt` excerpt bundled with this binary.
+    pub fn load_embedded() -> Self {
+        ScriptTable {
+            ranges: parse_scripts_txt(EMBEDDED_SCRIPTS_TXT),
+        }
+    }
+
+    /// Loads a `Scripts.txt` file from disk, for callers who want full
+    /// UCD coverage instead of the bundled excerpt.
+    pub fn load_from_path(path: &Path) -> io::Result<Self> {
+        let contents = fs::read_to_string(path)?;
+        Ok(ScriptTable {
+            ranges: parse_scripts_txt(&contents),
+        })
+    }
+
+    /// Returns the Script name for `codepoint`, or [`UNKNOWN_SCRIPT`] if
+    /// it isn't covered by any range in the table.
+    pub fn lookup(&self, codepoint: u32) -> &str {
+        match self
+            .ranges
+            .binary_search_by(|(start, end, _)| {
+                if codepoint < *start {
+                    std::cmp::Ordering::Greater
+                } else if codepoint > *end {
+                    std::cmp::Ordering::Less
+                } else {
+                    std::cmp::Ordering::Equal
+                }
+            }) {
+            Ok(index) => &self.ranges[index].2,
+            Err(_) => UNKNOWN_SCRIPT,
+        }
+    }
+}
+
+/// Parses the `codepoint_or_range ; ScriptName # comment` lines of a
+/// `Scripts.txt` file into a range table sorted by start codepoint.
+fn parse_scripts_txt(contents: &str) -> Vec<(u32, u32, String)> {
+    let mut ranges: Vec<(u32, u32, String)> = contents
+        .lines()
+        .filter_map(|line| {
+            let line = match line.find('#') {
+                Some(index) => &line[..index],
+                None => line,
+            };
+            let line = line.trim();
+            if line.is_empty() {
+                return None;
+            }
+            let mut fields = line.splitn(2, ';');
+            let codepoints = fields.next()?.trim();
+            let script = fields.next()?.trim();
+            let (start, end) = match codepoints.split_once("..") {
+                Some((start, end)) => (
+                    u32::from_str_radix(start, 16).ok()?,
+                    u32::from_str_radix(end, 16).ok()?,
+                ),
+                None => {
+                    let point = u32::from_str_radix(codepoints, 16).ok()?;
+                    (point, point)
+                }
+            };
+            Some((start, end, script.to_string()))
+        })
+        .collect();
+    ranges.sort_by_key(|(start, _, _)| *start);
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_latin_and_cyrillic() {
+        let table = ScriptTable::load_embedded();
+        assert_eq!(table.lookup('A' as u32), "Latin");
+        assert_eq!(table.lookup(0x0430), "Cyrillic");
+    }
+
+    #[test]
+    fn resolves_range_boundaries() {
+        let table = ScriptTable::load_embedded();
+        assert_eq!(table.lookup(0x0370), "Greek");
+        assert_eq!(table.lookup(0x0373), "Greek");
+        assert_eq!(table.lookup(0x0374), "Common");
+    }
+
+    #[test]
+    fn unassigned_codepoint_is_unknown() {
+        let table = ScriptTable::load_embedded();
+        assert_eq!(table.lookup(0x10FFFF), UNKNOWN_SCRIPT);
+    }
+
+    #[test]
+    fn parses_single_codepoint_entries() {
+        let ranges = parse_scripts_txt("00AA ; Latin # FEMININE ORDINAL INDICATOR\n");
+        assert_eq!(ranges, vec![(0x00AA, 0x00AA, "Latin".to_string())]);
+    }
+
+    #[test]
+    fn parses_out_of_order_ranges_sorted() {
+        let ranges = parse_scripts_txt("0400..04FF ; Cyrillic\n0041..005A ; Latin\n");
+        assert_eq!(ranges[0].0, 0x0041);
+        assert_eq!(ranges[1].0, 0x0400);
+    }
+
+    #[test]
+    fn skips_blank_and_comment_only_lines() {
+        let ranges = parse_scripts_txt("\n# just a comment\n0041..005A ; Latin\n");
+        assert_eq!(ranges, vec![(0x0041, 0x005A, "Latin".to_string())]);
+    }
+}